@@ -0,0 +1,82 @@
+use std::ptr;
+
+/// A safe-to-use view over a region of memory that may be mutated at any instant by another
+/// process (e.g. a `shared_memory::Shmem` mapping).
+///
+/// Plain slices (`slice::from_raw_parts` + `copy_from_slice`) assume the optimizer is free to
+/// reorder, cache, or split accesses, which is undefined behavior the moment a foreign writer can
+/// touch the same bytes concurrently - the reader could observe a torn or invalid value mid-copy.
+/// `SharedBuffer` instead routes every access through `read_volatile`/`write_volatile`, one element
+/// at a time, so each byte access is a single, non-elidable, non-reorderable memory operation.
+///
+/// `SharedBuffer` does not itself guarantee freedom from torn *reads* across multiple bytes (a
+/// concurrent writer can still change byte 5 after byte 4 was read); callers that need a
+/// consistent multi-byte snapshot must pair this with higher-level coordination, such as the
+/// seqlock in [`crate::DoubleBufferedSharedMemory`].
+pub struct SharedBuffer {
+    ptr: *mut u8,
+    len: usize,
+}
+
+impl SharedBuffer {
+    /// Wraps a raw pointer to `len` bytes of (possibly externally-mutated) memory.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be valid for volatile reads and writes of `len` bytes for the lifetime of the
+    /// returned `SharedBuffer`.
+    pub unsafe fn new(ptr: *mut u8, len: usize) -> Self {
+        Self { ptr, len }
+    }
+
+    /// Total number of bytes covered by this buffer.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Copies `min(out.len(), self.len() - offset)` bytes starting at `offset` into `out`, one
+    /// volatile read at a time, and returns how many bytes were copied.
+    pub fn read_at(&self, offset: usize, out: &mut [u8]) -> usize {
+        if offset >= self.len {
+            return 0;
+        }
+        let count = out.len().min(self.len - offset);
+        unsafe {
+            for i in 0..count {
+                let src = self.ptr.add(offset + i);
+                out[i] = ptr::read_volatile(src);
+            }
+        }
+        count
+    }
+
+    /// Copies `min(data.len(), self.len() - offset)` bytes from `data` into the buffer starting at
+    /// `offset`, one volatile write at a time, and returns how many bytes were copied.
+    pub fn write_at(&self, offset: usize, data: &[u8]) -> usize {
+        if offset >= self.len {
+            return 0;
+        }
+        let count = data.len().min(self.len - offset);
+        unsafe {
+            for i in 0..count {
+                let dst = self.ptr.add(offset + i);
+                ptr::write_volatile(dst, data[i]);
+            }
+        }
+        count
+    }
+
+    /// Reads the entire buffer into a freshly allocated, private `Vec<u8>`.
+    ///
+    /// Deserializing directly out of shared memory would let a concurrent foreign write tear the
+    /// bytes bincode is parsing; a private volatile copy like this one is safe to deserialize from.
+    pub fn read_to_vec(&self) -> Vec<u8> {
+        let mut out = vec![0u8; self.len];
+        self.read_at(0, &mut out);
+        out
+    }
+}
@@ -3,6 +3,7 @@ pub enum DbShmError {
     InvalidSize(usize, usize), // Aggiunto: dimensione attesa, dimensione reale
     SerializationError(String, usize, usize), // Aggiunto: messaggio di errore, dimensione attesa, dimensione reale
     DeserializationError(String, usize, usize), // Aggiunto: messaggio di errore, dimensione attesa, dimensione reale
+    RemapError(String), // Errore durante la ri-mappatura di un buffer in crescita
 }
 
 impl std::fmt::Display for DbShmError {
@@ -17,6 +18,9 @@ impl std::fmt::Display for DbShmError {
             DbShmError::DeserializationError(msg, expected, actual) => {
                 write!(f, "Deserialization error: {}. Expected size: {}, Actual size: {}", msg, expected, actual)
             },
+            DbShmError::RemapError(msg) => {
+                write!(f, "Failed to grow and remap shared memory buffer: {}", msg)
+            },
         }
     }
 }
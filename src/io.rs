@@ -0,0 +1,135 @@
+use crate::buffer::SharedBuffer;
+use crate::{DoubleBufferedSharedMemory, BUFFER_HEADER_LEN};
+use num_traits::Zero;
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+use std::io;
+use std::sync::atomic::Ordering;
+
+/// A [`std::io::Write`] cursor over the buffer a [`DoubleBufferedSharedMemory`] will publish next.
+///
+/// Obtained via [`DoubleBufferedSharedMemory::writer`]. Bytes written through it land directly in
+/// the mapped buffer (via [`SharedBuffer`]) rather than in an intermediate `Vec<u8>`. Writes past
+/// the buffer's current capacity return a short count, exactly like `BufWriter`/`BufReader` do at
+/// their own boundaries - the `Writer` does not grow the buffer mid-stream. The write is published
+/// (buffer flipped, generation made even again) on `flush()` or when the `Writer` is dropped.
+///
+/// Publishing is one-shot: once `flush()` (or `drop`) has published, the buffer it was writing to
+/// is exposed to readers, so any further `write()` would tear data they may already be reading
+/// without re-marking the generation odd. Further `write()` calls after that point return an
+/// error instead - get a new `Writer` via [`DoubleBufferedSharedMemory::writer`] for the next frame.
+pub struct Writer<'a, T>
+where
+    T: Serialize + for<'de> Deserialize<'de> + Any + Zero + Clone,
+{
+    dbshm: &'a mut DoubleBufferedSharedMemory<T>,
+    write_index: usize,
+    capacity: usize,
+    offset: usize,
+    published: bool,
+}
+
+impl<'a, T> Writer<'a, T>
+where
+    T: Serialize + for<'de> Deserialize<'de> + Any + Zero + Clone,
+{
+    pub(crate) fn new(dbshm: &'a mut DoubleBufferedSharedMemory<T>) -> Self {
+        let write_index = dbshm.active_index().load(Ordering::Relaxed) as usize;
+        // The whole streaming session is the seqlock's "odd" window, not just one memcpy: readers
+        // back off until `flush`/`drop` publishes it.
+        dbshm.generation().fetch_add(1, Ordering::AcqRel);
+        let capacity = dbshm.buffer_header(write_index).0 as usize;
+        Self {
+            dbshm,
+            write_index,
+            capacity,
+            offset: 0,
+            published: false,
+        }
+    }
+
+    fn publish(&mut self) {
+        if self.published {
+            return;
+        }
+        let shared = unsafe {
+            SharedBuffer::new(
+                self.dbshm.buffers[self.write_index].as_ptr(),
+                BUFFER_HEADER_LEN,
+            )
+        };
+        shared.write_at(8, &(self.offset as u64).to_le_bytes());
+
+        self.dbshm
+            .active_index()
+            .store(1 - self.write_index as u64, Ordering::Release);
+        self.dbshm.generation().fetch_add(1, Ordering::Release);
+        self.published = true;
+    }
+}
+
+impl<'a, T> io::Write for Writer<'a, T>
+where
+    T: Serialize + for<'de> Deserialize<'de> + Any + Zero + Clone,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.published {
+            return Err(io::Error::other(
+                "Writer already published via flush()/drop; get a new writer() for the next frame",
+            ));
+        }
+        if self.offset >= self.capacity {
+            return Ok(0);
+        }
+        let remaining = self.capacity - self.offset;
+        let n = buf.len().min(remaining);
+
+        let shared = unsafe {
+            SharedBuffer::new(
+                self.dbshm.buffers[self.write_index].as_ptr(),
+                BUFFER_HEADER_LEN + self.capacity,
+            )
+        };
+        shared.write_at(BUFFER_HEADER_LEN + self.offset, &buf[..n]);
+        self.offset += n;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.publish();
+        Ok(())
+    }
+}
+
+impl<'a, T> Drop for Writer<'a, T>
+where
+    T: Serialize + for<'de> Deserialize<'de> + Any + Zero + Clone,
+{
+    fn drop(&mut self) {
+        self.publish();
+    }
+}
+
+/// A [`std::io::Read`] cursor over a private snapshot of the inactive buffer's current payload.
+///
+/// Obtained via [`DoubleBufferedSharedMemory::reader`]. The snapshot is taken once, up front,
+/// through the same seqlock-guarded path as [`DoubleBufferedSharedMemory::read`]; reading from the
+/// `Reader` afterwards never touches shared memory again, so it can be handed to `io::copy` or any
+/// decoder that targets `io::Read` without further synchronization.
+pub struct Reader {
+    cursor: io::Cursor<Vec<u8>>,
+}
+
+impl Reader {
+    pub(crate) fn new(data: Vec<u8>) -> Self {
+        Self {
+            cursor: io::Cursor::new(data),
+        }
+    }
+}
+
+impl io::Read for Reader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        io::Read::read(&mut self.cursor, buf)
+    }
+}
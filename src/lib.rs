@@ -1,31 +1,67 @@
 extern crate shared_memory;
+pub mod buffer;
 pub mod errors;
+pub mod io;
+pub mod view;
+use buffer::SharedBuffer;
 use shared_memory::*;
-use std::sync::{Arc, Mutex, Condvar};
 use errors::DbShmError;
 use std::any::Any;
 use std::marker::PhantomData;
-use ndarray::{ArrayD, IxDyn};
+use ndarray::{ArrayD, ArrayView, ArrayViewD, IxDyn};
 use bincode;
 use serde::{Serialize, Deserialize};
 use std::mem;
 use num_traits::Zero;
 use std::error::Error;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::hint;
+use std::thread;
+use view::{PodDType, RawHeader, RawView};
+
+// Size in bytes of the control segment: `generation`, `active_index`, then one `view_guard` per
+// buffer (index 2 and 3) counting outstanding `read_view()` borrows on that buffer - all
+// `AtomicU64`, back to back, so every process mapping "{name_base}_ctrl" sees the same words.
+// The guards live here rather than on the struct because they must be visible to *any* process
+// that might try to grow/remap a buffer, not just the one that produced the `read_view`.
+const CONTROL_SEGMENT_SIZE: usize = mem::size_of::<u64>() * 4;
+
+// Maximum number of seqlock retries `read_view()` performs before giving up and returning an
+// error, rather than handing back a view that may be torn.
+const MAX_READ_VIEW_RETRIES: u32 = 1000;
+
+// Size in bytes of the per-buffer header prepended to each data buffer: a `capacity` (the room
+// available for the payload, excluding this header) and a `len` (the size of the payload actually
+// written), both little-endian `u64`s. Storing these alongside the bytes - rather than only in the
+// struct - lets a buffer grow independently of the others and lets `read`/`read_view` trust the
+// header's `len` instead of assuming the construction-time shape still applies.
+const BUFFER_HEADER_LEN: usize = mem::size_of::<u64>() * 2;
 
 /// Structure for managing shared memory with double buffering.
 /// This structure is generic over `T` which must implement Serialize, Deserialize, Any, Zero, and Clone.
 /// It provides synchronized read and write operations to a shared memory space with double buffering to minimize waiting time.
+///
+/// Coordination between the writer and any number of readers - including readers living in other
+/// processes - happens through a small control segment ("{name_base}_ctrl") mapped alongside the
+/// two data buffers. The control segment holds a `generation` counter and an `active_index`, both
+/// `AtomicU64`, forming a seqlock: the writer bumps `generation` to an odd value before copying
+/// into the buffer `active_index` names, flips `active_index`, then bumps `generation` back to
+/// even, so readers (which read the other, inactive buffer) see the new data. Readers
+/// read optimistically and retry whenever they observe an odd or changing generation, so a reader
+/// never has to block a writer and never sees a half-written buffer.
 pub struct DoubleBufferedSharedMemory<T> {
-    // Internal shared memory buffers
+    // Internal shared memory buffers. Each one is prefixed with a `BUFFER_HEADER_LEN`-byte header
+    // (capacity, len) and can be individually grown and remapped by `ensure_buffer_capacity`.
     buffers: Vec<shared_memory::Shmem>,
-    // Index of the currently active buffer for writing
-    active_index: usize,
-    // Total size of each buffer
+    // Base name used to derive "{name_base}_0", "{name_base}_1" and "{name_base}_ctrl". Kept
+    // around so a buffer can be dropped and recreated under the same os_id when it needs to grow.
+    name_base: String,
+    // Control segment shared by every process mapping the same `name_base`, holding the
+    // `generation` and `active_index` atomics used by the seqlock.
+    control: shared_memory::Shmem,
+    // Default per-buffer payload capacity computed from the shape passed to `new()`. Buffers are
+    // created with this much room and may later grow past it; it no longer bounds `write()`.
     size: usize,
-    // Permits for synchronized read access
-    read_permits: Arc<(Mutex<usize>, Condvar)>,
-    // Permits for synchronized write access
-    write_permits: Arc<(Mutex<bool>, Condvar)>,
     // PhantomData to associate generic type T with the struct without storing it
     _phantom: PhantomData<T>,
 }
@@ -33,7 +69,7 @@ pub struct DoubleBufferedSharedMemory<T> {
 
 impl<T> DoubleBufferedSharedMemory<T>
 where
-    T: Serialize + Deserialize<'static> + Any + Zero + Clone,
+    T: Serialize + for<'de> Deserialize<'de> + Any + Zero + Clone,
 {
     /// Creates a new instance of `DoubleBufferedSharedMemory`.
     ///
@@ -57,74 +93,187 @@ where
         let base_size = shape.0 * shape.1 * shape.2 * dtype_size;
 
         let extra_size = Self::calc_extra_size(base_size, shape)?;
-        let size = base_size + extra_size; // Dimensione totale necessaria
+        let size = base_size + extra_size; // Capacità di payload iniziale per ciascun buffer
         let mut buffers = Vec::new();
 
         for i in 0..2 {
             let name = format!("{}_{}", name_base, i);
             let shm = ShmemConf::new()
-                .size(size)
+                .size(BUFFER_HEADER_LEN + size)
                 .os_id(&name)
                 .create()?;
+            // Header iniziale: capacity = size appena allocata, len = 0 (nessun payload ancora).
+            let shared = unsafe { SharedBuffer::new(shm.as_ptr(), BUFFER_HEADER_LEN) };
+            shared.write_at(0, &(size as u64).to_le_bytes());
+            shared.write_at(8, &0u64.to_le_bytes());
             buffers.push(shm);
         }
+
+        let control_name = format!("{}_ctrl", name_base);
+        let control = ShmemConf::new()
+            .size(CONTROL_SEGMENT_SIZE)
+            .os_id(&control_name)
+            .create()?;
+        // All four words start at zero: an even generation (0) means "no write in progress",
+        // active_index 0 matches the freshly created buffers, and no buffer has an outstanding
+        // read_view() borrow yet.
+        unsafe {
+            for word in 0..4 {
+                (control.as_ptr().add(mem::size_of::<u64>() * word) as *const AtomicU64)
+                    .as_ref()
+                    .unwrap()
+                    .store(0, Ordering::Relaxed);
+            }
+        }
+
         Ok(Self {
             buffers,
-            active_index: 0,
+            name_base: name_base.to_string(),
+            control,
             size,
-            read_permits: Arc::new((Mutex::new(1), Condvar::new())), // Permette una lettura alla volta
-            write_permits: Arc::new((Mutex::new(true), Condvar::new())), // Permette una scrittura alla volta
             _phantom: PhantomData,
         })
     }
 
-
-    fn calc_extra_size(base_size: usize, shape: (usize, usize, usize)) -> Result<usize, DbShmError> {
-        let shape_dyn = IxDyn(&[shape.0, shape.1, shape.2]);
-        // Calcola la dimensione necessaria per la serializzazione di un piccolo campione di dati
-        let sample_data = ArrayD::<T>::zeros(shape_dyn); // Crea un array di zeri
-        let serialized_sample = bincode::serialize(&sample_data)
-        .map_err(|e| DbShmError::SerializationError(e.to_string(), base_size, sample_data.len()))?;
-        let extra_size = serialized_sample.len() - base_size; // Calcola lo spazio extra necessario
-        Ok(extra_size)
+    /// Reads the `(capacity, len)` header stored at the front of buffer `idx`.
+    fn buffer_header(&self, idx: usize) -> (u64, u64) {
+        let shared = unsafe { SharedBuffer::new(self.buffers[idx].as_ptr(), BUFFER_HEADER_LEN) };
+        let mut bytes = [0u8; BUFFER_HEADER_LEN];
+        shared.read_at(0, &mut bytes);
+        let capacity = u64::from_le_bytes(bytes[0..8].try_into().unwrap());
+        let len = u64::from_le_bytes(bytes[8..16].try_into().unwrap());
+        (capacity, len)
     }
-    /// Acquires the write permit. This function blocks the thread until the write permit is available.
-    fn acquire_write_permit(&self) {
-        let (lock, cvar) = &*self.write_permits;
-        let mut permit = lock.lock().unwrap();
-        while !*permit {
-            permit = cvar.wait(permit).unwrap();
+
+    /// Grows and remaps buffer `idx` if its current capacity can't hold `required_payload_len`
+    /// bytes, doubling (at least) like `Vec::reserve` does, and returns the buffer's capacity
+    /// after the call (unchanged if no growth was needed).
+    ///
+    /// The buffer's existing payload and `len` are copied forward across the remap. Callers only
+    /// grow the write-target buffer they are about to overwrite, never the inactive buffer
+    /// readers are currently reading from.
+    fn ensure_buffer_capacity(&mut self, idx: usize, required_payload_len: usize) -> Result<usize, DbShmError> {
+        let (capacity, len) = self.buffer_header(idx);
+        if required_payload_len as u64 <= capacity {
+            return Ok(capacity as usize);
+        }
+
+        if self.view_guard(idx).load(Ordering::Acquire) != 0 {
+            // A `read_view()` borrow (in this process or another) is still outstanding on this
+            // buffer. Dropping and remapping it now would leave that view pointing at unmapped
+            // memory, so refuse instead - the caller can retry once the view is dropped.
+            return Err(DbShmError::RemapError(format!(
+                "buffer {} has an outstanding read_view() borrow; refusing to grow/remap it",
+                idx
+            )));
         }
-        *permit = false;
+
+        let mut new_capacity = capacity.max(1);
+        while new_capacity < required_payload_len as u64 {
+            new_capacity *= 2;
+        }
+
+        let existing_payload = {
+            let shared = unsafe {
+                SharedBuffer::new(self.buffers[idx].as_ptr(), BUFFER_HEADER_LEN + capacity as usize)
+            };
+            let mut payload = vec![0u8; len as usize];
+            shared.read_at(BUFFER_HEADER_LEN, &mut payload);
+            payload
+        };
+
+        let name = format!("{}_{}", self.name_base, idx);
+        // Drop the old mapping first so its OS-level segment is unlinked and `os_id` can be reused.
+        drop(self.buffers.remove(idx));
+        let new_shm = ShmemConf::new()
+            .size(BUFFER_HEADER_LEN + new_capacity as usize)
+            .os_id(&name)
+            .create()
+            .map_err(|e| DbShmError::RemapError(e.to_string()))?;
+        self.buffers.insert(idx, new_shm);
+
+        let shared = unsafe {
+            SharedBuffer::new(self.buffers[idx].as_ptr(), BUFFER_HEADER_LEN + new_capacity as usize)
+        };
+        shared.write_at(0, &new_capacity.to_le_bytes());
+        shared.write_at(8, &len.to_le_bytes());
+        shared.write_at(BUFFER_HEADER_LEN, &existing_payload);
+
+        Ok(new_capacity as usize)
+    }
+
+    /// Pre-grows the buffer the next `write()`/`write_raw()` will target, so that call doesn't
+    /// need to remap mid-write.
+    ///
+    /// Only the write-target buffer is touched: the *other* buffer is the one `read()`,
+    /// `read_view()` and `reader()` may be volatile-reading from right now, and dropping +
+    /// recreating it here - while a concurrent reader could be mid-`read_at` on it - would leave
+    /// that reader touching unmapped memory. That buffer grows lazily instead, the next time it
+    /// becomes the write target.
+    pub fn reserve(&mut self, shape: (usize, usize, usize)) -> Result<(), DbShmError> {
+        let dtype_size = mem::size_of::<T>();
+        let base_size = shape.0 * shape.1 * shape.2 * dtype_size;
+        let extra_size = Self::calc_extra_size(base_size, shape)?;
+        // Room for either layout: `write_raw`'s header is larger than bincode's framing.
+        let raw_required = RawHeader::new(0, &[shape.0, shape.1, shape.2]).encoded_len() + base_size;
+        let required = (base_size + extra_size).max(raw_required);
+        let write_index = self.active_index().load(Ordering::Acquire) as usize;
+        self.ensure_buffer_capacity(write_index, required)?;
+        Ok(())
+    }
+
+    /// Returns the length in bytes of the payload most recently published by `write()` or
+    /// `write_raw()`, as recorded in that buffer's header - not the construction-time shape size.
+    pub fn current_len(&self) -> usize {
+        let write_index = self.active_index().load(Ordering::Acquire) as usize;
+        let read_index = 1 - write_index;
+        self.buffer_header(read_index).1 as usize
     }
 
-    /// Releases the write permit.
-    fn release_write_permit(&self) {
-        let (lock, cvar) = &*self.write_permits;
-        let mut permit = lock.lock().unwrap();
-        *permit = true;
-        cvar.notify_all();
+    /// Returns a reference to the shared `generation` counter living in the control segment.
+    ///
+    /// An odd value means a writer is currently copying into the write-target buffer; an even value
+    /// means the buffers are in a consistent state.
+    fn generation(&self) -> &AtomicU64 {
+        unsafe { (self.control.as_ptr() as *const AtomicU64).as_ref().unwrap() }
     }
 
-    /// Acquires the read permit. This function blocks the thread until the read permit is available.
-    fn acquire_read_permit(&self) {
-        let (lock, cvar) = &*self.read_permits;
-        let mut permit = lock.lock().unwrap();
-        while *permit == 0 {
-            permit = cvar.wait(permit).unwrap();
+    /// Returns a reference to the shared `active_index` counter living in the control segment.
+    ///
+    /// This holds the index of the buffer the next `write()` will target, exactly like the old
+    /// per-instance `active_index` field, except every process mapping `name_base` observes the
+    /// same value.
+    fn active_index(&self) -> &AtomicU64 {
+        unsafe {
+            (self.control.as_ptr().add(mem::size_of::<u64>()) as *const AtomicU64)
+                .as_ref()
+                .unwrap()
         }
-        *permit -= 1;
     }
 
-    /// Releases the read permit.
-    fn release_read_permit(&self) {
-        let (lock, cvar) = &*self.read_permits;
-        let mut permit = lock.lock().unwrap();
-        *permit += 1;
-        cvar.notify_all();
+    /// Returns a reference to the shared outstanding-`read_view()`-borrow counter for buffer
+    /// `idx`, living in the control segment so every process sees the same count.
+    ///
+    /// `ensure_buffer_capacity` refuses to drop and remap a buffer while its guard is non-zero,
+    /// so a live `RawView` can never be left pointing at unmapped memory.
+    fn view_guard(&self, idx: usize) -> &AtomicU64 {
+        unsafe {
+            (self.control.as_ptr().add(mem::size_of::<u64>() * (2 + idx)) as *const AtomicU64)
+                .as_ref()
+                .unwrap()
+        }
     }
 
-    
+
+    fn calc_extra_size(base_size: usize, shape: (usize, usize, usize)) -> Result<usize, DbShmError> {
+        let shape_dyn = IxDyn(&[shape.0, shape.1, shape.2]);
+        // Calcola la dimensione necessaria per la serializzazione di un piccolo campione di dati
+        let sample_data = ArrayD::<T>::zeros(shape_dyn); // Crea un array di zeri
+        let serialized_sample = bincode::serialize(&sample_data)
+        .map_err(|e| DbShmError::SerializationError(e.to_string(), base_size, sample_data.len()))?;
+        let extra_size = serialized_sample.len() - base_size; // Calcola lo spazio extra necessario
+        Ok(extra_size)
+    }
     /// Writes data to the active buffer.
     ///
     /// # Parameters
@@ -144,63 +293,127 @@ where
     /// dbshm.write(&array).expect("Failed to write to shared memory");
     /// 
     pub fn write(&mut self, array: &ArrayD<T>) -> Result<(), DbShmError> {
-        self.acquire_write_permit();
         let data = bincode::serialize(array)
         .map_err(|e| DbShmError::SerializationError(e.to_string(), self.size, array.len()))?;
 
-        if data.len() != self.size {
-            self.release_write_permit();
-            return Err(DbShmError::InvalidSize(self.size, data.len()));
-        }
-        
-        let active_buffer = &mut self.buffers[self.active_index];
-        let buffer_slice = unsafe {
-            // Ottieni un riferimento mutable ai tuoi dati attivi
-            std::slice::from_raw_parts_mut(active_buffer.as_ptr() as *mut u8, self.size)
-        };
-    
-        // Copia gli ultimi self.size bytes da data a buffer_slice
-        buffer_slice.copy_from_slice(&data);
+        // `write_index` is the buffer the seqlock currently considers "next to write", i.e. the
+        // one a concurrent reader treats as stale. We write into it, then flip `active_index` so
+        // readers see it as the fresh one. If `data` no longer fits - e.g. the producer's shape
+        // changed - the buffer grows to make room instead of failing.
+        let write_index = self.active_index().load(Ordering::Relaxed) as usize;
+        let capacity = self.ensure_buffer_capacity(write_index, data.len())?;
+
+        // Odd generation announces "a write is in progress" to any concurrent reader.
+        self.generation().fetch_add(1, Ordering::AcqRel);
+
+        let target_buffer = &self.buffers[write_index];
+        // Il buffer è mappato anche nell'altro processo: tutti gli accessi passano da
+        // `SharedBuffer`, che usa read/write volatile invece di assumere che la memoria sia stabile.
+        let shared = unsafe { SharedBuffer::new(target_buffer.as_ptr(), BUFFER_HEADER_LEN + capacity) };
+        shared.write_at(BUFFER_HEADER_LEN, &data);
+        shared.write_at(8, &(data.len() as u64).to_le_bytes()); // aggiorna il len nell'header
 
-        self.active_index = 1 - self.active_index; // Cambia il buffer attivo
-        self.release_write_permit();
+        self.active_index().store(1 - write_index as u64, Ordering::Release);
+        // Back to even: publishes the write and unblocks any reader spinning on it.
+        self.generation().fetch_add(1, Ordering::Release);
         Ok(())
 
     }
 
     /// Reads data from the inactive buffer.
     ///
-    /// This method deserializes and returns the data from the currently inactive buffer.
-    /// It ensures that only one read operation can occur at a time through the use of read permits.
+    /// This method deserializes and returns the data from the currently inactive buffer. It never
+    /// blocks: it optimistically snapshots the buffer and retries, via the seqlock formed by the
+    /// control segment's `generation` counter, if a concurrent writer published in the meantime.
     ///
     /// # Return
     ///
     /// Returns a `Result` containing an `ArrayD<T>` array if the read is successful,
     /// or a `DbShmError` in case of problems during reading.
     pub fn read(&self) -> Result<ArrayD<T>, DbShmError> {
-        self.acquire_read_permit();
+        let data = self.snapshot_inactive();
+        bincode::deserialize(&data)
+            .map_err(|e| DbShmError::SerializationError(e.to_string(), self.size, data.len()))
+    }
+
+    /// Takes a private, race-safe snapshot of the inactive buffer's current payload.
+    ///
+    /// This is the seqlock retry loop shared by [`Self::read`] and [`Self::reader`]: it
+    /// optimistically copies the bytes the last `write()`/`write_raw()` published (per the
+    /// header's `len`, not the construction-time shape) and retries if a concurrent writer
+    /// changed the generation while the copy was in flight.
+    fn snapshot_inactive(&self) -> Vec<u8> {
+        let mut retries: u32 = 0;
+        loop {
+            let gen_before = self.generation().load(Ordering::Acquire);
+            if gen_before & 1 != 0 {
+                // A writer is mid-copy; back off and try again.
+                Self::backoff(&mut retries);
+                continue;
+            }
 
-        let read_index = 1 - self.active_index;
-        let inactive_buffer = &self.buffers[read_index];
-        // let start_index = inactive_buffer.len().wrapping_sub(self.size);
+            let write_index = self.active_index().load(Ordering::Acquire) as usize;
+            let read_index = 1 - write_index;
+            let inactive_buffer = &self.buffers[read_index];
+            let (capacity, len) = self.buffer_header(read_index);
 
-        
-        // Ottieni una slice che inizia da start_index e si estende per self.size bytes.
-        let data = unsafe {
-            std::slice::from_raw_parts(inactive_buffer.as_ptr() as *const u8, self.size)
-        };
-        let deserialized_data = bincode::deserialize(data)
-        .map_err(|e| DbShmError::SerializationError(e.to_string(), self.size, data.len()))?;
-        self.release_read_permit();
-        Ok(deserialized_data)
+            // Copia privata tramite accessi volatile: non deserializziamo mai direttamente dalla
+            // memoria condivisa, che un altro processo potrebbe modificare in qualunque istante.
+            let shared = unsafe {
+                SharedBuffer::new(inactive_buffer.as_ptr(), BUFFER_HEADER_LEN + capacity as usize)
+            };
+            let mut snapshot = vec![0u8; len as usize];
+            shared.read_at(BUFFER_HEADER_LEN, &mut snapshot);
+
+            let gen_after = self.generation().load(Ordering::Acquire);
+            if gen_after == gen_before {
+                return snapshot;
+            }
+            // The writer published (or started another write) while we were copying; the
+            // snapshot may be torn, so discard it and retry the whole read.
+            Self::backoff(&mut retries);
+        }
+    }
+
+    /// Returns a [`std::io::Write`] cursor over the write-target buffer.
+    ///
+    /// The seqlock's `generation` is marked odd as soon as the `Writer` is created - covering the
+    /// whole streaming session, not just a single memcpy - and is published (buffer flipped,
+    /// generation made even again) on `flush()` or when the `Writer` is dropped. This lets a
+    /// caller pipe any encoder that targets `io::Write` (e.g. a compressor or a framed codec)
+    /// straight into shared memory via `io::copy`, without first materializing a `Vec<u8>`.
+    pub fn writer(&mut self) -> io::Writer<'_, T> {
+        io::Writer::new(self)
+    }
+
+    /// Returns a [`std::io::Read`] cursor snapshotting the inactive buffer's current payload.
+    ///
+    /// The snapshot is taken once, at creation time, via the same race-safe path as
+    /// [`Self::read`]; subsequent `read()` calls on the returned `Reader` just serve bytes out of
+    /// that private copy, so it composes with `io::copy` and any decoder that targets `io::Read`.
+    pub fn reader(&self) -> io::Reader {
+        io::Reader::new(self.snapshot_inactive())
     }
 
-    /// Returns the size of the shared memory buffer.
+    /// Spin-with-yield backoff used while retrying a seqlock read.
     ///
-    /// This method retrieves the total size in bytes of the allocated shared memory buffer
-    /// used by the instance of `DoubleBufferedSharedMemory`. This size is determined during
-    /// the creation of the `DoubleBufferedSharedMemory` instance and remains constant
-    /// throughout its lifetime.
+    /// A few iterations spin in place (cheap, and usually enough since a write only holds the odd
+    /// generation for the duration of a memcpy); after that we yield the thread so a slow writer
+    /// on another core doesn't starve the scheduler.
+    fn backoff(retries: &mut u32) {
+        if *retries < 64 {
+            hint::spin_loop();
+        } else {
+            thread::yield_now();
+        }
+        *retries = retries.saturating_add(1);
+    }
+
+    /// Returns the initial per-buffer payload capacity computed from the shape passed to `new()`.
+    ///
+    /// This is the *construction-time* size and does not change even if `write()`/`write_raw()`
+    /// later grow a buffer past it; use [`Self::current_len()`] for the size of the payload
+    /// actually stored right now.
     ///
     /// # Examples
     ///
@@ -245,8 +458,256 @@ where
             // In molti casi, questo non è strettamente necessario poiché Rust rilascia automaticamente
             // le risorse quando un oggetto esce dallo scope, ma lo includiamo qui per completezza
             // e per esprimere esplicitamente l'intenzione di rilasciare la risorsa.
-            
+
+        }
+    }
+
+}
+
+impl<T> DoubleBufferedSharedMemory<T>
+where
+    T: Serialize + for<'de> Deserialize<'de> + Any + Zero + Clone + PodDType,
+{
+    /// Writes `array` to the active buffer using the raw layout instead of bincode.
+    ///
+    /// The raw layout is a fixed header (magic, dtype tag, shape, element count) followed by the
+    /// little-endian bytes of each element with no framing in between. It exists for `T: Pod`
+    /// numeric types where the cost of a bincode round-trip dominates at high frame rates; use
+    /// [`Self::write`] for non-`Pod` types. Pairs with [`Self::read_view`], which borrows the
+    /// written bytes back without allocating or deserializing.
+    pub fn write_raw(&mut self, array: &ArrayView<T, IxDyn>) -> Result<(), DbShmError> {
+        let header = RawHeader::new(T::DTYPE_TAG, array.shape());
+        let header_bytes = header.encode();
+        let element_bytes: &[u8] = bytemuck::cast_slice(array.as_slice().ok_or_else(|| {
+            DbShmError::SerializationError(
+                "write_raw requires a contiguous array".to_string(),
+                self.size,
+                array.len(),
+            )
+        })?);
+
+        let payload_len = header_bytes.len() + element_bytes.len();
+
+        let write_index = self.active_index().load(Ordering::Relaxed) as usize;
+        let capacity = self.ensure_buffer_capacity(write_index, payload_len)?;
+        self.generation().fetch_add(1, Ordering::AcqRel);
+
+        let target_buffer = &self.buffers[write_index];
+        let shared = unsafe { SharedBuffer::new(target_buffer.as_ptr(), BUFFER_HEADER_LEN + capacity) };
+        shared.write_at(BUFFER_HEADER_LEN, &header_bytes);
+        shared.write_at(BUFFER_HEADER_LEN + header_bytes.len(), element_bytes);
+        shared.write_at(8, &(payload_len as u64).to_le_bytes());
+
+        self.active_index().store(1 - write_index as u64, Ordering::Release);
+        self.generation().fetch_add(1, Ordering::Release);
+        Ok(())
+    }
+
+    /// Returns a zero-copy view over the last buffer written with [`Self::write_raw`].
+    ///
+    /// Unlike [`Self::read`], this does not allocate or run bincode: it validates the header and
+    /// then borrows the element bytes directly out of the inactive buffer, bitcasting them to `T`
+    /// via `bytemuck`. The header is checked against a pre/post `generation` check (the same
+    /// seqlock [`Self::snapshot_inactive`] uses, but bounded here by [`MAX_READ_VIEW_RETRIES`]
+    /// rather than retrying forever, since there's no private snapshot to fall back on), and the
+    /// returned [`RawView`] keeps that buffer's `view_guard` incremented for as long as it's
+    /// alive, so `ensure_buffer_capacity` refuses to drop and remap the buffer out from under it.
+    pub fn read_view(&self) -> Result<RawView<'_, T>, DbShmError> {
+        let mut retries: u32 = 0;
+        loop {
+            let gen_before = self.generation().load(Ordering::Acquire);
+            if gen_before & 1 != 0 {
+                // A writer is mid-copy; back off and try again.
+                if retries >= MAX_READ_VIEW_RETRIES {
+                    return Err(DbShmError::DeserializationError(
+                        "read_view: gave up waiting for a writer to finish publishing".to_string(),
+                        0,
+                        0,
+                    ));
+                }
+                Self::backoff(&mut retries);
+                continue;
+            }
+
+            let read_index = 1 - self.active_index().load(Ordering::Acquire) as usize;
+            let (capacity, len) = self.buffer_header(read_index);
+            let inactive_buffer = &self.buffers[read_index];
+            let shared = unsafe {
+                SharedBuffer::new(inactive_buffer.as_ptr(), BUFFER_HEADER_LEN + capacity as usize)
+            };
+
+            let mut prefix = [0u8; 16];
+            shared.read_at(BUFFER_HEADER_LEN, &mut prefix);
+            let header_len = RawHeader::peek_encoded_len(&prefix)?;
+            let mut header_bytes = vec![0u8; header_len];
+            shared.read_at(BUFFER_HEADER_LEN, &mut header_bytes);
+            let (header, data_offset) = RawHeader::decode(&header_bytes, T::DTYPE_TAG)?;
+
+            let elem_bytes_len = header.elem_count * mem::size_of::<T>();
+            if (data_offset + elem_bytes_len) as u64 > len {
+                return Err(DbShmError::InvalidSize(len as usize, data_offset + elem_bytes_len));
+            }
+
+            let gen_after = self.generation().load(Ordering::Acquire);
+            if gen_after != gen_before {
+                // A writer published (or started another write) while we were reading the header
+                // above; it may be torn, so discard it and retry the whole read.
+                if retries >= MAX_READ_VIEW_RETRIES {
+                    return Err(DbShmError::DeserializationError(
+                        "read_view: gave up waiting for a consistent generation".to_string(),
+                        gen_before as usize,
+                        gen_after as usize,
+                    ));
+                }
+                Self::backoff(&mut retries);
+                continue;
+            }
+
+            // Zero-copy: borrow the element bytes directly out of the mapped buffer rather than
+            // going through `SharedBuffer::read_to_vec`. The generation check above guards against
+            // a concurrent writer tearing the header we just read; the `RawView` wrapping `view`
+            // below is what protects the element bytes themselves for as long as the caller holds
+            // it, by keeping `ensure_buffer_capacity` from remapping this buffer underneath them.
+            let element_bytes = unsafe {
+                std::slice::from_raw_parts(
+                    (inactive_buffer.as_ptr() as *const u8).add(BUFFER_HEADER_LEN + data_offset),
+                    elem_bytes_len,
+                )
+            };
+            let elements: &[T] = bytemuck::cast_slice(element_bytes);
+            let view = ArrayViewD::from_shape(IxDyn(&header.shape), elements).map_err(|e| {
+                DbShmError::DeserializationError(e.to_string(), header.elem_count, elements.len())
+            })?;
+            return Ok(RawView::new(view, self.view_guard(read_index)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+
+    #[test]
+    fn write_then_read_round_trips() {
+        let mut dbshm =
+            DoubleBufferedSharedMemory::<u8>::new("dbshm_test_rw_roundtrip", (4, 4, 1)).unwrap();
+        let written = ArrayD::<u8>::from_shape_fn(IxDyn(&[4, 4, 1]), |idx| (idx[0] * 4 + idx[1]) as u8);
+        dbshm.write(&written).unwrap();
+        let read_back = dbshm.read().unwrap();
+        assert_eq!(read_back, written);
+    }
+
+    #[test]
+    fn write_raw_then_read_view_round_trips() {
+        let mut dbshm =
+            DoubleBufferedSharedMemory::<f32>::new("dbshm_test_write_raw_view", (2, 3, 1)).unwrap();
+        let written = ArrayD::<f32>::from_shape_fn(IxDyn(&[2, 3, 1]), |idx| (idx[0] * 3 + idx[1]) as f32);
+        dbshm.write_raw(&written.view()).unwrap();
+        let view = dbshm.read_view().unwrap();
+        assert_eq!(view.to_owned(), written);
+    }
+
+    #[test]
+    fn read_view_gives_up_on_a_stuck_odd_generation() {
+        let dbshm =
+            DoubleBufferedSharedMemory::<f32>::new("dbshm_test_torn_read", (2, 2, 1)).unwrap();
+        // Simulate a writer that never finishes publishing: read_view must not spin forever, it
+        // should bail out with an error once MAX_READ_VIEW_RETRIES is exhausted.
+        dbshm.generation().fetch_add(1, Ordering::Relaxed);
+        let result = dbshm.read_view();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn growing_past_initial_capacity_updates_current_len() {
+        let mut dbshm =
+            DoubleBufferedSharedMemory::<u8>::new("dbshm_test_growth", (2, 2, 1)).unwrap();
+        let small = ArrayD::<u8>::zeros(IxDyn(&[2, 2, 1]));
+        dbshm.write(&small).unwrap();
+        let small_len = dbshm.current_len();
+
+        // Much bigger than the constructor's initial capacity, forcing ensure_buffer_capacity to
+        // grow and remap the write-target buffer.
+        let big = ArrayD::<u8>::from_shape_fn(IxDyn(&[64, 64, 3]), |idx| (idx[0] + idx[1]) as u8);
+        dbshm.write(&big).unwrap();
+        let big_len = dbshm.current_len();
+
+        assert!(big_len > small_len);
+        let read_back = dbshm.read().unwrap();
+        assert_eq!(read_back, big);
+    }
+
+    /// Opens a second, independent handle onto shared memory an earlier `DoubleBufferedSharedMemory::new`
+    /// already created under `name_base`, the way a reader living in another process would - as
+    /// opposed to a second `&mut` borrow of the same handle, which the borrow checker already
+    /// forbids from interleaving `read_view()` with `write_raw()` on one instance.
+    fn open_existing(name_base: &str) -> DoubleBufferedSharedMemory<f32> {
+        let buffers = (0..2)
+            .map(|i| {
+                ShmemConf::new()
+                    .os_id(format!("{}_{}", name_base, i))
+                    .open()
+                    .unwrap()
+            })
+            .collect();
+        let control = ShmemConf::new()
+            .os_id(format!("{}_ctrl", name_base))
+            .open()
+            .unwrap();
+        DoubleBufferedSharedMemory {
+            buffers,
+            name_base: name_base.to_string(),
+            control,
+            size: 0,
+            _phantom: PhantomData,
         }
     }
 
+    #[test]
+    fn ensure_buffer_capacity_refuses_to_remap_a_buffer_with_an_outstanding_view() {
+        let name_base = "dbshm_test_view_guard";
+        let mut writer = DoubleBufferedSharedMemory::<f32>::new(name_base, (2, 2, 1)).unwrap();
+        let small = ArrayD::<f32>::zeros(IxDyn(&[2, 2, 1]));
+        writer.write_raw(&small.view()).unwrap();
+
+        // A second handle on the same name_base, standing in for a reader in another process.
+        let reader = open_existing(name_base);
+
+        // Holding this view keeps buffer 0's view_guard non-zero - and `writer` observes the same
+        // counter, since it lives in the shared control segment, not either handle's own memory.
+        let view = reader.read_view().unwrap();
+
+        // ...flip the active buffer with a write that targets buffer 1, leaving buffer 0 alone...
+        let unrelated = ArrayD::<f32>::zeros(IxDyn(&[2, 2, 1]));
+        writer.write_raw(&unrelated.view()).unwrap();
+
+        // ...so this write lands back on buffer 0 and, being much bigger, must grow it - which
+        // should be refused while `reader`'s `view` is still alive.
+        let big = ArrayD::<f32>::from_shape_fn(IxDyn(&[64, 64, 3]), |idx| (idx[0] + idx[1]) as f32);
+        let result = writer.write_raw(&big.view());
+        assert!(matches!(result, Err(DbShmError::RemapError(_))));
+
+        drop(view);
+        // Once the view is dropped, the same write succeeds.
+        writer.write_raw(&big.view()).unwrap();
+    }
+
+    #[test]
+    fn writer_and_reader_round_trip_arbitrary_bytes_via_io_copy() {
+        let mut dbshm =
+            DoubleBufferedSharedMemory::<u8>::new("dbshm_test_io_copy", (4, 4, 1)).unwrap();
+        let payload: Vec<u8> = (0..37u8).collect();
+
+        {
+            let mut writer = dbshm.writer();
+            writer.write_all(&payload).unwrap();
+            writer.flush().unwrap();
+        }
+
+        let mut reader = dbshm.reader();
+        let mut out = Vec::new();
+        reader.read_to_end(&mut out).unwrap();
+        assert_eq!(out, payload);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,202 @@
+use crate::errors::DbShmError;
+use ndarray::ArrayViewD;
+use std::ops::Deref;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Magic bytes identifying the raw (non-bincode) layout written by `write_raw`: ASCII "DBSH".
+pub const MAGIC: u32 = 0x4853_4244;
+
+/// A zero-copy view returned by [`crate::DoubleBufferedSharedMemory::read_view`], borrowed
+/// directly from the mapped buffer.
+///
+/// Holding a `RawView` keeps the originating buffer's shared `view_guard` counter (in the control
+/// segment, visible to every process) incremented for as long as it's alive. `ensure_buffer_capacity`
+/// checks that counter before it would otherwise drop and remap the buffer, so growth refuses to
+/// run (rather than leaving this view pointing at unmapped memory) until every outstanding
+/// `RawView` on that buffer has been dropped.
+pub struct RawView<'a, T> {
+    view: ArrayViewD<'a, T>,
+    guard: &'a AtomicU64,
+}
+
+impl<'a, T> RawView<'a, T> {
+    pub(crate) fn new(view: ArrayViewD<'a, T>, guard: &'a AtomicU64) -> Self {
+        guard.fetch_add(1, Ordering::AcqRel);
+        Self { view, guard }
+    }
+}
+
+impl<'a, T> Deref for RawView<'a, T> {
+    type Target = ArrayViewD<'a, T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.view
+    }
+}
+
+impl<'a, T> Drop for RawView<'a, T> {
+    fn drop(&mut self) {
+        self.guard.fetch_sub(1, Ordering::Release);
+    }
+}
+
+/// Associates a POD numeric type with the tag stored in the raw layout header, so `read_view` can
+/// refuse to reinterpret bytes written for a different element type.
+///
+/// Implemented only for the fixed-width numeric types that make sense as image/tensor elements;
+/// anything else should keep using the bincode `read()`/`write()` path.
+pub trait PodDType: bytemuck::Pod {
+    const DTYPE_TAG: u32;
+}
+
+macro_rules! impl_pod_dtype {
+    ($($ty:ty => $tag:expr),* $(,)?) => {
+        $(
+            impl PodDType for $ty {
+                const DTYPE_TAG: u32 = $tag;
+            }
+        )*
+    };
+}
+
+impl_pod_dtype! {
+    u8 => 1,
+    i8 => 2,
+    u16 => 3,
+    i16 => 4,
+    u32 => 5,
+    i32 => 6,
+    f32 => 7,
+    f64 => 8,
+}
+
+/// Header written before the raw element bytes: magic, dtype tag, shape, and element count.
+///
+/// Layout (all little-endian): `magic: u32, dtype_tag: u32, ndim: u64, shape: [u64; ndim],
+/// elem_count: u64`, followed immediately by `elem_count` elements of the tagged type.
+pub struct RawHeader {
+    pub dtype_tag: u32,
+    pub shape: Vec<usize>,
+    pub elem_count: usize,
+}
+
+impl RawHeader {
+    pub fn new(dtype_tag: u32, shape: &[usize]) -> Self {
+        let elem_count = shape.iter().product();
+        Self {
+            dtype_tag,
+            shape: shape.to_vec(),
+            elem_count,
+        }
+    }
+
+    /// Size in bytes of the fixed-width prefix, before the variable-length shape array.
+    const FIXED_PREFIX_LEN: usize = 4 + 4 + 8;
+
+    /// Total size in bytes of this header once encoded (excluding the raw element bytes).
+    pub fn encoded_len(&self) -> usize {
+        Self::FIXED_PREFIX_LEN + self.shape.len() * 8 + 8
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(self.encoded_len());
+        out.extend_from_slice(&MAGIC.to_le_bytes());
+        out.extend_from_slice(&self.dtype_tag.to_le_bytes());
+        out.extend_from_slice(&(self.shape.len() as u64).to_le_bytes());
+        for dim in &self.shape {
+            out.extend_from_slice(&(*dim as u64).to_le_bytes());
+        }
+        out.extend_from_slice(&(self.elem_count as u64).to_le_bytes());
+        out
+    }
+
+    /// Peeks at the fixed-width prefix to determine how many bytes the full header occupies,
+    /// without requiring the shape array to already be in `bytes`.
+    ///
+    /// `bytes` must contain at least `FIXED_PREFIX_LEN` bytes.
+    pub fn peek_encoded_len(bytes: &[u8]) -> Result<usize, DbShmError> {
+        if bytes.len() < Self::FIXED_PREFIX_LEN {
+            return Err(DbShmError::DeserializationError(
+                "raw layout header truncated".to_string(),
+                Self::FIXED_PREFIX_LEN,
+                bytes.len(),
+            ));
+        }
+        let ndim = u64::from_le_bytes(bytes[8..16].try_into().unwrap()) as usize;
+        Self::header_len_for_ndim(ndim)
+    }
+
+    /// Computes `FIXED_PREFIX_LEN + ndim * 8 + 8` with checked arithmetic.
+    ///
+    /// `ndim` is read straight out of shared memory before any seqlock validation can confirm it
+    /// wasn't torn mid-write, so a concurrent writer (or adversarial input) can hand this an
+    /// arbitrary `u64`-derived value; an unchecked multiply/add here would panic (debug) or wrap
+    /// into a bogus, too-small length that corrupts a later allocation (release).
+    fn header_len_for_ndim(ndim: usize) -> Result<usize, DbShmError> {
+        ndim.checked_mul(8)
+            .and_then(|shape_bytes| shape_bytes.checked_add(Self::FIXED_PREFIX_LEN + 8))
+            .ok_or_else(|| {
+                DbShmError::DeserializationError(
+                    format!("raw layout header ndim out of range: {}", ndim),
+                    usize::MAX,
+                    ndim,
+                )
+            })
+    }
+
+    /// Decodes a header from the front of `bytes`, returning the header and the offset at which
+    /// the raw element bytes begin.
+    pub fn decode(bytes: &[u8], expected_dtype_tag: u32) -> Result<(Self, usize), DbShmError> {
+        if bytes.len() < Self::FIXED_PREFIX_LEN {
+            return Err(DbShmError::DeserializationError(
+                "raw layout header truncated".to_string(),
+                Self::FIXED_PREFIX_LEN,
+                bytes.len(),
+            ));
+        }
+        let magic = u32::from_le_bytes(bytes[0..4].try_into().unwrap());
+        if magic != MAGIC {
+            return Err(DbShmError::DeserializationError(
+                format!("bad magic in raw layout header: {:#x}", magic),
+                MAGIC as usize,
+                magic as usize,
+            ));
+        }
+        let dtype_tag = u32::from_le_bytes(bytes[4..8].try_into().unwrap());
+        if dtype_tag != expected_dtype_tag {
+            return Err(DbShmError::DeserializationError(
+                format!(
+                    "raw layout dtype tag mismatch: expected {}, found {}",
+                    expected_dtype_tag, dtype_tag
+                ),
+                expected_dtype_tag as usize,
+                dtype_tag as usize,
+            ));
+        }
+        let ndim = u64::from_le_bytes(bytes[8..16].try_into().unwrap()) as usize;
+
+        let elem_count_end = Self::header_len_for_ndim(ndim)?;
+        let shape_end = elem_count_end - 8;
+        if bytes.len() < elem_count_end {
+            return Err(DbShmError::DeserializationError(
+                "raw layout header truncated before shape/elem_count".to_string(),
+                elem_count_end,
+                bytes.len(),
+            ));
+        }
+
+        let mut shape = Vec::with_capacity(ndim);
+        for i in 0..ndim {
+            let start = 16 + i * 8;
+            shape.push(u64::from_le_bytes(bytes[start..start + 8].try_into().unwrap()) as usize);
+        }
+        let elem_count = u64::from_le_bytes(bytes[shape_end..elem_count_end].try_into().unwrap()) as usize;
+
+        let header = Self {
+            dtype_tag,
+            shape,
+            elem_count,
+        };
+        Ok((header, elem_count_end))
+    }
+}